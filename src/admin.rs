@@ -0,0 +1,26 @@
+use clap::Subcommand;
+use uuid::Uuid;
+
+use crate::db::Db;
+
+#[derive(Subcommand)]
+pub enum AdminCommand {
+    /// Insert an item directly, bypassing the API
+    CreateItem {
+        #[arg(long)]
+        name: String,
+    },
+}
+
+pub async fn run(pool: &Db, command: AdminCommand) -> anyhow::Result<()> {
+    match command {
+        AdminCommand::CreateItem { name } => {
+            let id = Uuid::new_v4();
+            sqlx::query!("INSERT INTO items (id, name) VALUES ($1, $2)", id, name)
+                .execute(pool)
+                .await?;
+            tracing::info!(%id, %name, "item created");
+        }
+    }
+    Ok(())
+}
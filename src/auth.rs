@@ -0,0 +1,240 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, State},
+    http::{header, request::Parts, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::error::Error;
+use crate::state::AppState;
+
+#[derive(Deserialize)]
+pub struct RegisterRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Deserialize)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct AuthResponse {
+    pub access_token: String,
+}
+
+/// JWT claims minted on login. `session_epoch` lets us invalidate every
+/// previously issued token for a user without maintaining a denylist.
+///
+/// `exp` stays second-resolution, as required by the JWT `NumericDate`
+/// convention `jsonwebtoken`'s validator expects. `session_epoch` is
+/// microsecond-resolution: truncating it to whole seconds let a
+/// `logout-all` issued in the same second as a token's mint time compare
+/// equal and fail to revoke that token.
+#[derive(Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub exp: i64,
+    pub session_epoch: i64,
+}
+
+/// Microsecond-resolution Unix timestamp for `session_epoch` comparisons.
+fn epoch_micros(dt: OffsetDateTime) -> i64 {
+    (dt.unix_timestamp_nanos() / 1_000) as i64
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/v1/auth/register", post(register))
+        .route("/v1/auth/login", post(login))
+        .route("/v1/auth/logout-all", post(logout_all))
+}
+
+fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty() && domain.contains('.')
+}
+
+fn mint_jwt(user_id: Uuid, session_epoch: i64, config: &crate::config::Config) -> Result<String, Error> {
+    let exp = OffsetDateTime::now_utc() + config.jwt_expiry;
+    let claims = Claims {
+        sub: user_id,
+        exp: exp.unix_timestamp(),
+        session_epoch,
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+    )
+    .map_err(|_| Error::Unauthorized)
+}
+
+pub async fn register(
+    State(state): State<AppState>,
+    Json(body): Json<RegisterRequest>,
+) -> Result<Json<AuthResponse>, Error> {
+    if !is_valid_email(&body.email) {
+        return Err(Error::EmailInvalid);
+    }
+
+    let salt = SaltString::generate(&mut OsRng);
+    let password_hash = Argon2::default()
+        .hash_password(body.password.as_bytes(), &salt)
+        .map_err(|_| Error::Unauthorized)?
+        .to_string();
+
+    let id = Uuid::new_v4();
+    let row = sqlx::query!(
+        "INSERT INTO users (id, email, password_hash) VALUES ($1, $2, $3) RETURNING session_epoch",
+        id,
+        body.email,
+        password_hash
+    )
+    .fetch_one(&state.pool)
+    .await;
+
+    let row = match row {
+        Ok(row) => row,
+        Err(err) if is_unique_violation(&err) => return Err(Error::EmailExists),
+        Err(err) => return Err(err.into()),
+    };
+
+    let access_token = mint_jwt(id, epoch_micros(row.session_epoch), &state.config)?;
+    Ok(Json(AuthResponse { access_token }))
+}
+
+/// Postgres signals a unique-constraint violation with SQLSTATE `23505`; we
+/// rely on the DB as the source of truth instead of a check-then-insert,
+/// which would race under concurrent registrations for the same email.
+fn is_unique_violation(err: &sqlx::Error) -> bool {
+    matches!(err.as_database_error().and_then(|e| e.code()), Some(code) if code == "23505")
+}
+
+pub async fn login(
+    State(state): State<AppState>,
+    Json(body): Json<LoginRequest>,
+) -> Result<Json<AuthResponse>, Error> {
+    let row = sqlx::query!(
+        "SELECT id, password_hash, session_epoch FROM users WHERE email = $1",
+        body.email
+    )
+    .fetch_optional(&state.pool)
+    .await?
+    .ok_or(Error::Unauthorized)?;
+
+    let parsed_hash = PasswordHash::new(&row.password_hash).map_err(|_| Error::Unauthorized)?;
+    Argon2::default()
+        .verify_password(body.password.as_bytes(), &parsed_hash)
+        .map_err(|_| Error::Unauthorized)?;
+
+    let access_token = mint_jwt(row.id, epoch_micros(row.session_epoch), &state.config)?;
+    Ok(Json(AuthResponse { access_token }))
+}
+
+/// Bumps the caller's `session_epoch`, immediately invalidating every token
+/// issued before this call without maintaining a denylist.
+pub async fn logout_all(
+    claims: AccessClaims,
+    State(state): State<AppState>,
+) -> Result<StatusCode, Error> {
+    sqlx::query!(
+        "UPDATE users SET session_epoch = now() WHERE id = $1",
+        claims.0.sub
+    )
+    .execute(&state.pool)
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Decodes and verifies a bearer token against `secret`. Shared by the
+/// `AccessClaims` extractor and anything else (e.g. the metering middleware)
+/// that needs to know who's calling without pulling in a full extractor.
+pub fn decode_claims(token: &str, secret: &str) -> Result<Claims, ()> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+    .map_err(|_| ())
+}
+
+/// Extractor that requires a valid, unexpired bearer token.
+pub struct AccessClaims(pub Claims);
+
+#[async_trait]
+impl FromRequestParts<AppState> for AccessClaims {
+    type Rejection = Error;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Self::Rejection> {
+        let header_value = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or(Error::Unauthorized)?;
+        let token = header_value
+            .strip_prefix("Bearer ")
+            .ok_or(Error::Unauthorized)?;
+
+        let claims = decode_claims(token, &state.config.jwt_secret).map_err(|_| Error::Unauthorized)?;
+
+        let row = sqlx::query!("SELECT session_epoch FROM users WHERE id = $1", claims.sub)
+            .fetch_optional(&state.pool)
+            .await?
+            .ok_or(Error::Unauthorized)?;
+
+        if !session_epoch_is_current(epoch_micros(row.session_epoch), claims.session_epoch) {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(AccessClaims(claims))
+    }
+}
+
+/// A token is still valid as long as the stored epoch hasn't moved past the
+/// one it was minted with; `logout-all` bumps the stored epoch to reject
+/// every token minted before that call.
+fn session_epoch_is_current(stored_epoch: i64, token_epoch: i64) -> bool {
+    stored_epoch <= token_epoch
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_emails_are_accepted() {
+        assert!(is_valid_email("user@example.com"));
+        assert!(is_valid_email("a@b.co"));
+    }
+
+    #[test]
+    fn invalid_emails_are_rejected() {
+        assert!(!is_valid_email("no-at-sign"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("user@nodot"));
+    }
+
+    #[test]
+    fn token_minted_at_the_current_epoch_is_valid() {
+        assert!(session_epoch_is_current(100, 100));
+    }
+
+    #[test]
+    fn token_minted_before_a_logout_all_is_rejected() {
+        assert!(!session_epoch_is_current(101, 100));
+    }
+}
@@ -0,0 +1,30 @@
+use clap::{Parser, Subcommand};
+
+use crate::admin::AdminCommand;
+
+#[derive(Parser)]
+#[command(name = "ooh-togglie", version, about = "Item service and admin CLI")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the HTTP server
+    Serve {
+        /// Overrides APP_HOST
+        #[arg(long)]
+        host: Option<String>,
+        /// Overrides APP_PORT
+        #[arg(long)]
+        port: Option<u16>,
+    },
+    /// Apply pending database migrations and exit
+    Migrate,
+    /// One-off maintenance tasks
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommand,
+    },
+}
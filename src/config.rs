@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+/// App-level settings sourced from the environment. Built once at startup and
+/// shared behind `AppState` rather than re-read per request.
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+    pub rate_limit_per_second: u64,
+    pub jwt_secret: String,
+    pub jwt_expiry: Duration,
+    pub cost_per_request: f64,
+    pub cost_per_cpu_second: f64,
+    pub metering_interval: Duration,
+}
+
+impl Config {
+    pub fn from_env() -> Self {
+        let host = std::env::var("APP_HOST").unwrap_or_else(|_| "0.0.0.0".into());
+        let port = std::env::var("APP_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(8080);
+        let rate_limit_per_second = std::env::var("APP_RATE_LIMIT_PER_SECOND")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(50);
+        let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET not set");
+        let jwt_expiry_secs: u64 = std::env::var("JWT_EXPIRY_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3600);
+        let cost_per_request = std::env::var("COST_PER_REQUEST")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.01);
+        let cost_per_cpu_second = std::env::var("COST_PER_CPU")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0.05);
+        let metering_interval_secs: u64 = std::env::var("METERING_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(60);
+
+        Config {
+            host,
+            port,
+            rate_limit_per_second,
+            jwt_secret,
+            jwt_expiry: Duration::from_secs(jwt_expiry_secs),
+            cost_per_request,
+            cost_per_cpu_second,
+            metering_interval: Duration::from_secs(metering_interval_secs),
+        }
+    }
+}
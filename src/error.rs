@@ -0,0 +1,43 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+
+/// Crate-wide error type. Every fallible handler returns `Result<_, Error>` so
+/// failures surface as structured JSON instead of being swallowed.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Sqlx(#[from] sqlx::Error),
+
+    #[error("resource not found")]
+    NotFound,
+
+    #[error("invalid email address")]
+    EmailInvalid,
+
+    #[error("unauthorized")]
+    Unauthorized,
+
+    #[error("an account with this email already exists")]
+    EmailExists,
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Error::Sqlx(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::EmailInvalid => StatusCode::BAD_REQUEST,
+            Error::Unauthorized => StatusCode::UNAUTHORIZED,
+            Error::EmailExists => StatusCode::CONFLICT,
+        };
+
+        if let Error::Sqlx(err) = &self {
+            tracing::error!(%err, "database error");
+        }
+
+        let body = Json(json!({ "status": "error", "message": self.to_string() }));
+        (status, body).into_response()
+    }
+}
@@ -1,12 +1,18 @@
+use std::collections::HashMap;
 use std::net::SocketAddr;
-use axum::{routing::{get, post}, Router};
+use std::sync::Arc;
+use std::time::Duration;
+use axum::{routing::get, Router};
 use axum_prometheus::PrometheusMetricLayer;
+use clap::Parser;
+use tokio::sync::RwLock;
 use tower::{ServiceBuilder, limit::RateLimitLayer};
 use tower_http::trace::TraceLayer;
-use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 use utopia_swagger_ui::SwaggerUi;
 
-mod config; mod telemetry; mod routes; mod state; mod db;
+mod admin; mod auth; mod cli; mod config; mod error; mod metering; mod telemetry; mod routes; mod state; mod db;
+
+use cli::{Cli, Command};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -19,42 +25,67 @@ struct ApiDoc;
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
+    telemetry::init();
+
+    let cli = Cli::parse();
+    let pool = db::init_pool().await?;
+
+    match cli.command {
+        Command::Serve { host, port } => serve(pool, host, port).await,
+        Command::Migrate => {
+            sqlx::migrate!("./migrations").run(&pool).await?;
+            tracing::info!("migrations applied");
+            Ok(())
+        }
+        Command::Admin { command } => admin::run(&pool, command).await,
+    }
+}
+
+async fn serve(pool: db::Db, host: Option<String>, port: Option<u16>) -> anyhow::Result<()> {
+    let mut config = config::Config::from_env();
+    if let Some(host) = host {
+        config.host = host;
+    }
+    if let Some(port) = port {
+        config.port = port;
+    }
+    let config = Arc::new(config);
+
+    let app_state = state::AppState {
+        pool,
+        config: config.clone(),
+        usage: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    // Prometheus metrics layer
+    let (prom_layer, metric_handle) = PrometheusMetricLayer::pair();
+
+    let api = Router::new()
+        .merge(routes::health::router())
+        .merge(routes::items::router())
+        .merge(auth::router())
+        .merge(metering::router())
+        .route("/metrics", get(|| async move { metric_handle.render() }))
+        .layer(axum::middleware::from_fn_with_state(app_state.clone(), metering::track_usage));
+
+    let middleware = ServiceBuilder::new()
+        .layer(TraceLayer::new_for_http())
+        .layer(RateLimitLayer::new(config.rate_limit_per_second, Duration::from_secs(1)))
+        .layer(prom_layer);
+
+    let app = Router::new()
+        .merge(api)
+        .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .with_state(app_state.clone())
+        .layer(middleware);
+
+    let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+    let server = axum::Server::bind(&addr).serve(app.into_make_service());
 
-    //logging plus optional OpenTelemetry Bridge
-    let filter = EnvFilter::try_from_default_env()
-.unwrap_or_else(|_| EnvFilter::new("info"));
-let fmt_layer = fmt::layer().with_target(false);
-tracing_subscriber::registry().with(fmt_layer).init();
-
-let pool = db::init_pool().await?;
-let app_state = state::AppState { pool };
-
-// Prometheus metrics layer
-let (prom_layer, metric_handle) = PrometheusMetricLayer::pair();
-
-let api = Router::new()
-    .merge(routes::health::router())
-    .merge(routes::items::router())
-    .route("/metrics", get(|| async move { metric_handle.render() }));
-let rate_per_sec: u64 = std::env::var("APP_RATE_LIMIT_PER_SECOND").ok()
-    .and_then(|v| v.parse().ok()).unwrap_or(50);
-
-let middleware = ServiceBuilder::new()
-    .layer(TraceLayer::new_for_http())
-    .layer(RateLimitLayer::new(rate_per_sec, std::Duration::from_secs(1)))
-    .layer(prom_layer);
-
-let app = Router::new()
-    .merge(api)
-    .merge(SwaggerUi::new("/docs").url("/api-docs/openapi.json", ApiDoc::openapi()))
-    .with_state(app_state)
-    .layer(middleware);
-
-let host = std::env::var("APP_HOST").unwrap_or("0.0.0.0".into());
-let port = u16 = std::env::var("APP_PORT").ok().and_then(|p| p.parse().ok()).unwrap_or(8080);
-let addr: SocketAddr = format!("{}:{}", host, port).parse()?;
-
-tracing::info!(%addr, "listening");
-axum::Server::bind(&addr).serve(app.into_make_service()).await?;
-Ok(())
+    tracing::info!(%addr, "listening");
+    tokio::try_join!(
+        async { server.await.map_err(anyhow::Error::from) },
+        metering::run_billing_loop(app_state),
+    )?;
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use axum::extract::State;
+use axum::http::Request;
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::{routing::get, Json, Router};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::auth::AccessClaims;
+use crate::error::Error;
+use crate::state::AppState;
+
+/// Per-user resource usage accumulated since the last billing flush.
+///
+/// `wall_seconds` is wall-clock time spent serving the caller's requests
+/// (including I/O wait), not CPU time — it's a deliberately cheap proxy for
+/// the latter, not a claim that it measures CPU.
+#[derive(Default, Clone, Copy)]
+pub struct Usage {
+    pub requests: u64,
+    pub wall_seconds: f64,
+}
+
+#[derive(Serialize)]
+pub struct UsageResponse {
+    pub requests: u64,
+    pub wall_seconds: f64,
+    pub estimated_cost: f64,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/v1/usage", get(current_usage))
+}
+
+async fn current_usage(
+    claims: AccessClaims,
+    State(state): State<AppState>,
+) -> Result<Json<UsageResponse>, Error> {
+    let usage = state.usage.read().await;
+    let entry = usage.get(&claims.0.sub).copied().unwrap_or_default();
+    Ok(Json(UsageResponse {
+        requests: entry.requests,
+        wall_seconds: entry.wall_seconds,
+        estimated_cost: cost_of(&entry, &state.config),
+    }))
+}
+
+fn cost_of(usage: &Usage, config: &crate::config::Config) -> f64 {
+    (usage.requests as f64 / 1000.0) * config.cost_per_request
+        + usage.wall_seconds * config.cost_per_cpu_second
+}
+
+fn caller_id<B>(req: &Request<B>, secret: &str) -> Option<Uuid> {
+    let header_value = req
+        .headers()
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())?;
+    let token = header_value.strip_prefix("Bearer ")?;
+    crate::auth::decode_claims(token, secret).ok().map(|c| c.sub)
+}
+
+/// Tower middleware that attributes each request to its caller and tallies
+/// wall-clock time spent serving it.
+pub async fn track_usage<B>(State(state): State<AppState>, req: Request<B>, next: Next<B>) -> Response {
+    let user_id = caller_id(&req, &state.config.jwt_secret);
+    let started = Instant::now();
+    let response = next.run(req).await;
+
+    if let Some(user_id) = user_id {
+        let wall_seconds = started.elapsed().as_secs_f64();
+        let mut usage = state.usage.write().await;
+        let entry = usage.entry(user_id).or_default();
+        entry.requests += 1;
+        entry.wall_seconds += wall_seconds;
+    }
+
+    response
+}
+
+/// Background task that periodically flushes accumulated in-memory usage
+/// into the `usage` table and resets the counters. Intended to run alongside
+/// the axum server via `tokio::try_join!`.
+///
+/// The map is snapshotted and swapped for an empty one under a short-lived
+/// write lock, so `track_usage` and `/v1/usage` are only blocked for the
+/// swap itself, not for the duration of the DB flush. Accounts whose insert
+/// fails are merged back into the live map so their usage is retried on the
+/// next tick instead of being silently dropped.
+pub async fn run_billing_loop(state: AppState) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(state.config.metering_interval);
+    loop {
+        interval.tick().await;
+
+        let snapshot = std::mem::take(&mut *state.usage.write().await);
+        let mut failed = HashMap::new();
+
+        for (user_id, entry) in snapshot {
+            let cost = cost_of(&entry, &state.config);
+            if let Err(err) = sqlx::query!(
+                "INSERT INTO usage (user_id, period_start, requests, wall_seconds, cost) VALUES ($1, now(), $2, $3, $4)",
+                user_id,
+                entry.requests as i64,
+                entry.wall_seconds,
+                cost
+            )
+            .execute(&state.pool)
+            .await
+            {
+                tracing::error!(%err, %user_id, "failed to flush usage, will retry next period");
+                failed.insert(user_id, entry);
+            }
+        }
+
+        if !failed.is_empty() {
+            let mut usage = state.usage.write().await;
+            for (user_id, entry) in failed {
+                let live = usage.entry(user_id).or_default();
+                live.requests += entry.requests;
+                live.wall_seconds += entry.wall_seconds;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn test_config(cost_per_request: f64, cost_per_cpu_second: f64) -> crate::config::Config {
+        crate::config::Config {
+            host: "0.0.0.0".into(),
+            port: 8080,
+            rate_limit_per_second: 50,
+            jwt_secret: "test-secret".into(),
+            jwt_expiry: Duration::from_secs(3600),
+            cost_per_request,
+            cost_per_cpu_second,
+            metering_interval: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn cost_combines_per_request_and_per_cpu_second_rates() {
+        let config = test_config(0.01, 0.05);
+        let usage = Usage { requests: 2000, wall_seconds: 10.0 };
+        // (2000 / 1000) * 0.01 + 10.0 * 0.05
+        assert_eq!(cost_of(&usage, &config), 0.52);
+    }
+
+    #[test]
+    fn zero_usage_costs_nothing() {
+        let config = test_config(0.01, 0.05);
+        let usage = Usage::default();
+        assert_eq!(cost_of(&usage, &config), 0.0);
+    }
+}
@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::db::Db;
+use crate::metering::Usage;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: Db,
+    pub config: Arc<Config>,
+    pub usage: Arc<RwLock<HashMap<Uuid, Usage>>>,
+}
@@ -0,0 +1,9 @@
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+/// Sets up the global tracing subscriber. Kept separate from `main` so the
+/// optional OpenTelemetry bridge has a single place to hook in later.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let fmt_layer = fmt::layer().with_target(false);
+    tracing_subscriber::registry().with(fmt_layer).with(filter).init();
+}